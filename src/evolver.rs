@@ -0,0 +1,86 @@
+use std::cmp::Ordering;
+
+use crate::MLP;
+
+pub struct Evolver {
+    population: Vec<MLP>,
+    nin: usize,
+    nouts: Vec<usize>,
+    elite_frac: f64,
+    mutation_rate: f64,
+}
+
+impl Evolver {
+    pub fn new(
+        population_size: usize,
+        nin: usize,
+        nouts: &[usize],
+        elite_frac: f64,
+        mutation_rate: f64,
+    ) -> Self {
+        let population = (0..population_size).map(|_| MLP::new(nin, nouts)).collect();
+        Self {
+            population,
+            nin,
+            nouts: nouts.to_vec(),
+            elite_frac,
+            mutation_rate,
+        }
+    }
+
+    pub fn evolve(&mut self, fitness: impl Fn(&MLP) -> f64) {
+        let mut ranked: Vec<(f64, usize)> = self
+            .population
+            .iter()
+            .enumerate()
+            .map(|(i, mlp)| (fitness(mlp), i))
+            .collect();
+        ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        let n_elite = ((self.population.len() as f64 * self.elite_frac).round() as usize).max(1);
+        let elites: Vec<Vec<f64>> = ranked
+            .iter()
+            .take(n_elite)
+            .map(|&(_, i)| self.population[i].get_weights())
+            .collect();
+
+        self.population = (0..self.population.len())
+            .map(|i| {
+                let child = MLP::new(self.nin, &self.nouts);
+                if i == 0 {
+                    // Carry the incumbent best over unmutated so a bad
+                    // breeding round can never regress the population's best.
+                    child.set_weights(&elites[0]);
+                } else {
+                    child.set_weights(&self.breed(&elites));
+                }
+                child
+            })
+            .collect();
+    }
+
+    pub fn best(&self, fitness: impl Fn(&MLP) -> f64) -> &MLP {
+        self.population
+            .iter()
+            .max_by(|a, b| fitness(a).partial_cmp(&fitness(b)).unwrap_or(Ordering::Equal))
+            .unwrap()
+    }
+
+    fn breed(&self, elites: &[Vec<f64>]) -> Vec<f64> {
+        let parent_a = &elites[rand::random_range(0..elites.len())];
+        let parent_b = &elites[rand::random_range(0..elites.len())];
+
+        parent_a
+            .iter()
+            .zip(parent_b)
+            .map(|(&a, &b)| {
+                let gene = if rand::random_bool(0.5) { a } else { b };
+                if rand::random_bool(self.mutation_rate) {
+                    rand::random_range(-1.0..1.0)
+                } else {
+                    gene
+                }
+            })
+            .collect()
+    }
+}
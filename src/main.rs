@@ -1,7 +1,14 @@
+mod evolver;
+mod optimizer;
+mod tensor;
 mod value;
+use evolver::Evolver;
+use ndarray::array;
+use optimizer::{Adam, Optimizer, Sgd};
 use rand::Rng;
 use std::fmt::Display;
-use value::Value;
+use tensor::Tensor;
+use value::{cross_entropy, softmax, Value};
 fn main() {
     let mlp = MLP::new(3, &[4, 4, 1]);
     let xs = [
@@ -13,6 +20,7 @@ fn main() {
 
     let ys = [1.0, -1.0, -1.0, 1.0];
 
+    let mut opt = Adam::new(0.05, 0.9, 0.999, 1e-8);
     for _ in 0..100000 {
         let trial_ys = xs
             .iter()
@@ -25,7 +33,7 @@ fn main() {
             .map(|(y, y_hat)| (y - &Value::new(*y_hat)).pow(2.0))
             .fold(Value::new(0.0), |acc, x| (&acc + &x));
         loss.backward();
-        mlp.descend();
+        opt.step(&mlp.parameters());
     }
     let trial_ys = xs
         .iter()
@@ -40,6 +48,101 @@ fn main() {
 
     dbg!(&trial_ys);
     // println!("{}", mlp);
+
+    evolve_demo();
+    classification_demo();
+    tensor_demo();
+    sgd_demo();
+    save_load_demo();
+}
+
+fn save_load_demo() {
+    let original = MLP::new(3, &[4, 4, 1]);
+    let x = to_vals(&[2.0, 3.0, -1.0]);
+    let before = original.call(&x).first().unwrap().val();
+
+    let path = std::env::temp_dir().join("karp-rs-mlp-save-load-demo.txt");
+    original.save(path.to_str().unwrap()).unwrap();
+    let loaded = MLP::load(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let after = loaded.call(&to_vals(&[2.0, 3.0, -1.0])).first().unwrap().val();
+    assert_eq!(before, after, "save/load round-trip changed MLP output");
+}
+
+fn sgd_demo() {
+    let v = Value::new(10.0);
+    let mut opt = Sgd::new(0.1, 0.5);
+
+    for _ in 0..200 {
+        let loss = v.pow(2.0);
+        loss.backward();
+        opt.step(&[&v]);
+    }
+
+    assert!(v.val().abs() < 0.01, "Sgd should drive v toward 0, got {}", v.val());
+}
+
+fn tensor_demo() {
+    let x = Tensor::new(array![[1.0, 2.0], [3.0, 4.0]]);
+    let w = Tensor::new(array![[0.5, -0.5], [1.0, 1.0]]);
+    let b = Tensor::new(array![[0.1, 0.1]]);
+
+    let out = x.matmul(&w).add_bias(&b).relu().tanh();
+    out.backward();
+
+    let loss_with_w00 = |val: f64| -> f64 {
+        let mut data = w.data();
+        data[[0, 0]] = val;
+        let w2 = Tensor::new(data);
+        x.matmul(&w2).add_bias(&b).relu().tanh().data().sum()
+    };
+
+    let eps = 1e-5;
+    let base = w.data()[[0, 0]];
+    let numerical = (loss_with_w00(base + eps) - loss_with_w00(base - eps)) / (2.0 * eps);
+    let analytical = w.grad()[[0, 0]];
+
+    assert!(
+        (numerical - analytical).abs() < 1e-3,
+        "Tensor backward mismatch: numerical {numerical} vs analytical {analytical}"
+    );
+}
+
+fn classification_demo() {
+    let logits = vec![Value::new(2.0), Value::new(1.0), Value::new(0.1)];
+
+    let probs = softmax(&logits);
+    let total: f64 = probs.iter().map(Value::val).sum();
+    assert!((total - 1.0).abs() < 1e-9, "softmax should sum to 1, got {total}");
+
+    let loss = cross_entropy(&logits, 0);
+    loss.backward();
+    assert!(
+        logits[0].grad() < 0.0,
+        "cross_entropy should push the target logit up"
+    );
+}
+
+fn evolve_demo() {
+    let target = [0.5, -0.5, 1.0];
+    let fitness = |mlp: &MLP| -> f64 {
+        let out = mlp.call(&to_vals(&target));
+        -out.first().unwrap().val().abs()
+    };
+
+    let mut evolver = Evolver::new(30, 3, &[4, 1], 0.2, 0.1);
+    let initial_best = fitness(evolver.best(fitness));
+
+    for _ in 0..20 {
+        evolver.evolve(fitness);
+    }
+
+    let final_best = fitness(evolver.best(fitness));
+    assert!(
+        final_best >= initial_best,
+        "evolution did not improve fitness: {initial_best} -> {final_best}"
+    );
 }
 
 fn vals(vals: Vec<Value>) -> Vec<f64> {
@@ -144,6 +247,7 @@ impl Display for Neuron {
 }
 
 struct MLP {
+    nin: usize,
     layers: Vec<Layer>,
 }
 
@@ -154,7 +258,7 @@ impl MLP {
         for i in 1..nouts.len() {
             layers.push(Layer::new(nouts[i - 1], nouts[i]));
         }
-        Self { layers }
+        Self { nin, layers }
     }
 
     fn call(&self, x: &[Value]) -> Vec<Value> {
@@ -173,13 +277,77 @@ impl MLP {
         params
     }
 
-    fn descend(&self) {
-        for l in &self.layers {
-            for n in &l.neurons {
-                for w in &n.weights {
-                    w.descend();
-                }
-            }
+    pub fn get_weights(&self) -> Vec<f64> {
+        self.parameters().iter().map(|p| p.val()).collect()
+    }
+
+    pub fn set_weights(&self, weights: &[f64]) {
+        for (p, w) in self.parameters().iter().zip(weights) {
+            p.set_val(*w);
+        }
+    }
+
+    fn nouts(&self) -> Vec<usize> {
+        self.layers.iter().map(|l| l.neurons.len()).collect()
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let nouts = self.nouts();
+        let nouts_line: Vec<String> = nouts.iter().map(|n| n.to_string()).collect();
+        let weights_line: Vec<String> = self.get_weights().iter().map(|w| w.to_string()).collect();
+
+        let contents = format!(
+            "{}\n{}\n{}\n",
+            self.nin,
+            nouts_line.join(" "),
+            weights_line.join(" ")
+        );
+        std::fs::write(path, contents)
+    }
+
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        let contents = std::fs::read_to_string(path)?;
+        let mut lines = contents.lines();
+
+        let nin: usize = lines
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing nin line"))?
+            .trim()
+            .parse()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "malformed nin"))?;
+        let nouts: Vec<usize> = lines
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing nouts line"))?
+            .split_whitespace()
+            .map(|s| {
+                s.parse()
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, "malformed nouts"))
+            })
+            .collect::<std::io::Result<_>>()?;
+        let weights: Vec<f64> = lines
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing weights line"))?
+            .split_whitespace()
+            .map(|s| {
+                s.parse()
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, "malformed weight"))
+            })
+            .collect::<std::io::Result<_>>()?;
+
+        let mlp = MLP::new(nin, &nouts);
+        if weights.len() != mlp.parameters().len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "expected {} weights, found {}",
+                    mlp.parameters().len(),
+                    weights.len()
+                ),
+            ));
         }
+        mlp.set_weights(&weights);
+        Ok(mlp)
     }
 }
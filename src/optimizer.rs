@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use crate::value::Value;
+
+pub trait Optimizer {
+    fn step(&mut self, params: &[&Value]);
+}
+
+pub struct Sgd {
+    lr: f64,
+    momentum: f64,
+    velocity: HashMap<usize, f64>,
+}
+
+impl Sgd {
+    pub fn new(lr: f64, momentum: f64) -> Self {
+        Self {
+            lr,
+            momentum,
+            velocity: HashMap::new(),
+        }
+    }
+}
+
+impl Optimizer for Sgd {
+    fn step(&mut self, params: &[&Value]) {
+        for param in params {
+            let v = self.velocity.entry(param.id()).or_insert(0.0);
+            *v = self.momentum * *v + param.grad();
+            param.set_val(param.val() - self.lr * *v);
+        }
+    }
+}
+
+pub struct Adam {
+    lr: f64,
+    beta1: f64,
+    beta2: f64,
+    eps: f64,
+    t: i32,
+    m: HashMap<usize, f64>,
+    v: HashMap<usize, f64>,
+}
+
+impl Adam {
+    pub fn new(lr: f64, beta1: f64, beta2: f64, eps: f64) -> Self {
+        Self {
+            lr,
+            beta1,
+            beta2,
+            eps,
+            t: 0,
+            m: HashMap::new(),
+            v: HashMap::new(),
+        }
+    }
+}
+
+impl Default for Adam {
+    fn default() -> Self {
+        Self::new(0.001, 0.9, 0.999, 1e-8)
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, params: &[&Value]) {
+        self.t += 1;
+        for param in params {
+            let g = param.grad();
+            let m = self.m.entry(param.id()).or_insert(0.0);
+            let v = self.v.entry(param.id()).or_insert(0.0);
+            *m = self.beta1 * *m + (1.0 - self.beta1) * g;
+            *v = self.beta2 * *v + (1.0 - self.beta2) * g * g;
+
+            let mhat = *m / (1.0 - self.beta1.powi(self.t));
+            let vhat = *v / (1.0 - self.beta2.powi(self.t));
+            param.set_val(param.val() - self.lr * mhat / (vhat.sqrt() + self.eps));
+        }
+    }
+}
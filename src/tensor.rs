@@ -0,0 +1,188 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use ndarray::{Array2, Axis};
+
+#[derive(Clone)]
+pub struct Tensor(Rc<RefCell<TensorData>>);
+
+impl Debug for Tensor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tensor({:?})", self.0.borrow().data)
+    }
+}
+
+impl Tensor {
+    pub fn new(data: Array2<f64>) -> Self {
+        let grad = Array2::zeros(data.raw_dim());
+        Tensor(Rc::new(RefCell::new(TensorData {
+            data,
+            grad,
+            op: None,
+            kids: vec![],
+        })))
+    }
+
+    fn clone_ref(&self) -> Tensor {
+        Tensor(self.0.clone())
+    }
+
+    fn aug_grad(&self, grad: &Array2<f64>) {
+        self.0.borrow_mut().grad += grad;
+    }
+
+    pub fn data(&self) -> Array2<f64> {
+        self.0.borrow().data.clone()
+    }
+
+    pub fn grad(&self) -> Array2<f64> {
+        self.0.borrow().grad.clone()
+    }
+
+    fn postorder(&self, visited: &mut HashSet<usize>, topo: &mut Vec<Tensor>) {
+        let id = Rc::as_ptr(&self.0) as usize;
+        if visited.contains(&id) {
+            return;
+        }
+        visited.insert(id);
+
+        for kid in &self.0.borrow().kids {
+            kid.postorder(visited, topo);
+        }
+
+        topo.push(self.clone());
+    }
+
+    pub fn backward(&self) {
+        let mut visited = HashSet::new();
+        let mut topo = Vec::new();
+        self.postorder(&mut visited, &mut topo);
+
+        for t in &topo {
+            let shape = t.0.borrow().data.raw_dim();
+            t.0.borrow_mut().grad = Array2::zeros(shape);
+        }
+        let shape = self.0.borrow().data.raw_dim();
+        self.0.borrow_mut().grad = Array2::ones(shape);
+
+        for t in topo.into_iter().rev() {
+            if let Some(op) = &t.0.borrow().op {
+                let grad = t.0.borrow().grad.clone();
+                let val = t.0.borrow().data.clone();
+                op.backward(&t.0.borrow().kids, &grad, &val);
+            }
+        }
+    }
+
+    pub fn matmul(&self, other: &Tensor) -> Tensor {
+        let data = self.0.borrow().data.dot(&other.0.borrow().data);
+        new_node(MatMulOp, data, vec![self.clone_ref(), other.clone_ref()])
+    }
+
+    pub fn add(&self, other: &Tensor) -> Tensor {
+        let data = &self.0.borrow().data + &other.0.borrow().data;
+        new_node(AddOp, data, vec![self.clone_ref(), other.clone_ref()])
+    }
+
+    pub fn add_bias(&self, bias: &Tensor) -> Tensor {
+        self.add(bias)
+    }
+
+    pub fn mul(&self, other: &Tensor) -> Tensor {
+        let data = &self.0.borrow().data * &other.0.borrow().data;
+        new_node(MulOp, data, vec![self.clone_ref(), other.clone_ref()])
+    }
+
+    pub fn relu(&self) -> Tensor {
+        let data = self.0.borrow().data.mapv(|x| x.max(0.0));
+        new_node(ReluOp, data, vec![self.clone_ref()])
+    }
+
+    pub fn tanh(&self) -> Tensor {
+        let data = self.0.borrow().data.mapv(f64::tanh);
+        new_node(TanhOp, data, vec![self.clone_ref()])
+    }
+}
+
+fn new_node(op: impl TensorOp + 'static, data: Array2<f64>, kids: Vec<Tensor>) -> Tensor {
+    let grad = Array2::zeros(data.raw_dim());
+    Tensor(Rc::new(RefCell::new(TensorData {
+        data,
+        grad,
+        op: Some(Box::new(op)),
+        kids,
+    })))
+}
+
+struct TensorData {
+    data: Array2<f64>,
+    grad: Array2<f64>,
+    op: Option<Box<dyn TensorOp>>,
+    kids: Vec<Tensor>,
+}
+
+trait TensorOp {
+    fn backward(&self, inputs: &[Tensor], out_grad: &Array2<f64>, out_val: &Array2<f64>);
+}
+
+struct MatMulOp;
+
+impl TensorOp for MatMulOp {
+    fn backward(&self, inputs: &[Tensor], out_grad: &Array2<f64>, _out_val: &Array2<f64>) {
+        let a = &inputs[0];
+        let b = &inputs[1];
+        let a_data = a.0.borrow().data.clone();
+        let b_data = b.0.borrow().data.clone();
+        a.aug_grad(&out_grad.dot(&b_data.t()));
+        b.aug_grad(&a_data.t().dot(out_grad));
+    }
+}
+
+struct AddOp;
+
+impl TensorOp for AddOp {
+    fn backward(&self, inputs: &[Tensor], out_grad: &Array2<f64>, _out_val: &Array2<f64>) {
+        inputs[0].aug_grad(out_grad);
+
+        let bias_shape = inputs[1].0.borrow().data.raw_dim();
+        if bias_shape == out_grad.raw_dim() {
+            inputs[1].aug_grad(out_grad);
+        } else {
+            // Bias was a broadcast row vector: its gradient is the column
+            // sum of the upstream gradient, folded back to one row.
+            let summed = out_grad.sum_axis(Axis(0)).insert_axis(Axis(0));
+            inputs[1].aug_grad(&summed);
+        }
+    }
+}
+
+struct MulOp;
+
+impl TensorOp for MulOp {
+    fn backward(&self, inputs: &[Tensor], out_grad: &Array2<f64>, _out_val: &Array2<f64>) {
+        let a_data = inputs[0].0.borrow().data.clone();
+        let b_data = inputs[1].0.borrow().data.clone();
+        inputs[0].aug_grad(&(out_grad * &b_data));
+        inputs[1].aug_grad(&(out_grad * &a_data));
+    }
+}
+
+struct ReluOp;
+
+impl TensorOp for ReluOp {
+    fn backward(&self, inputs: &[Tensor], out_grad: &Array2<f64>, _out_val: &Array2<f64>) {
+        let mask = inputs[0].0.borrow().data.mapv(|x| if x > 0.0 { 1.0 } else { 0.0 });
+        inputs[0].aug_grad(&(out_grad * &mask));
+    }
+}
+
+struct TanhOp;
+
+impl TensorOp for TanhOp {
+    fn backward(&self, inputs: &[Tensor], out_grad: &Array2<f64>, out_val: &Array2<f64>) {
+        let derivative = out_val.mapv(|v| 1.0 - v * v);
+        inputs[0].aug_grad(&(out_grad * &derivative));
+    }
+}
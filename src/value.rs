@@ -2,7 +2,6 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Display;
-use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
 use std::rc::Rc;
 
@@ -24,7 +23,7 @@ impl Value {
         Value(self.0.clone())
     }
 
-    fn aug_grad(&self, grad: f64) {
+    pub fn aug_grad(&self, grad: f64) {
         self.0.borrow_mut().grad += grad;
     }
 
@@ -40,8 +39,11 @@ impl Value {
         self.0.borrow().grad
     }
 
-    pub fn descend(&self) {
-        self.0.borrow_mut().data = self.val() - 0.05 * self.grad();
+    /// A stable identity for this node, shared by every clone of the same
+    /// `Value`. Used by optimizers to key per-parameter state (e.g. Adam's
+    /// moment buffers) without requiring `Value` to implement `Hash`/`Eq`.
+    pub fn id(&self) -> usize {
+        Rc::as_ptr(&self.0) as usize
     }
 
     fn postorder(&self, visited: &mut HashSet<usize>, topo: &mut Vec<Value>) {
@@ -70,17 +72,34 @@ impl Value {
 
         for v in topo.into_iter().rev() {
             if let Some(op) = &v.0.borrow().op {
-                op.augment_kids(&v.0.borrow().kids, v.0.borrow().grad, v.val());
+                op.backward(&v.0.borrow().kids, v.0.borrow().grad, v.val());
             }
         }
     }
 }
 
+pub trait Op: Debug {
+    fn forward(&self, inputs: &[f64]) -> f64;
+    fn backward(&self, inputs: &[Value], out_grad: f64, out_val: f64);
+    fn symbol(&self) -> &str;
+}
+
+fn new_node(op: impl Op + 'static, kids: Vec<Value>) -> Value {
+    let inputs: Vec<f64> = kids.iter().map(Value::val).collect();
+    let data = op.forward(&inputs);
+    Value(Rc::new(RefCell::new(ValueData {
+        data,
+        grad: 0.0,
+        op: Some(Box::new(op)),
+        kids,
+    })))
+}
+
 #[derive(Debug)]
 struct ValueData {
     data: f64,
     grad: f64,
-    op: Option<Oper>,
+    op: Option<Box<dyn Op>>,
     kids: Vec<Value>,
 }
 
@@ -96,85 +115,171 @@ impl ValueData {
 }
 
 #[derive(Debug)]
-enum Oper {
-    Sum,
-    Mul,
-    Pow { exp: f64 },
-    Relu,
-    Tanh,
-    Exp,
-}
-
-impl Oper {
-    fn augment_kids(&self, children: &[Value], grad: f64, val: f64) {
-        match self {
-            Oper::Sum => {
-                for child in children {
-                    child.aug_grad(grad);
-                }
-            }
-            Oper::Mul => {
-                if children.len() == 2 {
-                    children[0].aug_grad(children[1].val() * grad);
-                    children[1].aug_grad(children[0].val() * grad);
-                }
-            }
-            Oper::Pow { exp } => {
-                if !children.is_empty() {
-                    let base = &children[0];
-                    base.aug_grad(exp * base.val().powf(exp - 1.0) * grad);
-                }
-            }
-            Oper::Relu => {
-                if !children.is_empty() {
-                    let a = &children[0];
-                    if a.val() > 0.0 {
-                        a.aug_grad(grad);
-                    }
-                }
-            }
-            Oper::Tanh => {
-                if !children.is_empty() {
-                    let a = &children[0];
-                    a.aug_grad(grad * (1.0 - (val * val)));
-                }
-            }
-            Oper::Exp => {
-                if !children.is_empty() {
-                    let a = &children[0];
-                    a.aug_grad(grad * val);
-                }
+struct SumOp;
+
+impl Op for SumOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs.iter().sum()
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, _out_val: f64) {
+        for child in inputs {
+            child.aug_grad(out_grad);
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        "+"
+    }
+}
+
+#[derive(Debug)]
+struct MulOp;
+
+impl Op for MulOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs.iter().product()
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, _out_val: f64) {
+        if inputs.len() == 2 {
+            inputs[0].aug_grad(inputs[1].val() * out_grad);
+            inputs[1].aug_grad(inputs[0].val() * out_grad);
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        "*"
+    }
+}
+
+#[derive(Debug)]
+struct PowOp {
+    exp: f64,
+}
+
+impl Op for PowOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs[0].powf(self.exp)
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, _out_val: f64) {
+        if !inputs.is_empty() {
+            let base = &inputs[0];
+            base.aug_grad(self.exp * base.val().powf(self.exp - 1.0) * out_grad);
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        "^"
+    }
+}
+
+#[derive(Debug)]
+struct ReluOp;
+
+impl Op for ReluOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs[0].max(0.0)
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, _out_val: f64) {
+        if !inputs.is_empty() {
+            let a = &inputs[0];
+            if a.val() > 0.0 {
+                a.aug_grad(out_grad);
             }
         }
     }
+
+    fn symbol(&self) -> &str {
+        "relu"
+    }
 }
 
-impl Display for Oper {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Oper::Sum => write!(f, "+"),
-            Oper::Mul => write!(f, "*"),
-            Oper::Pow { exp } => write!(f, "^{}", exp),
-            Oper::Relu => write!(f, "relu"),
-            Oper::Tanh => write!(f, "tanh"),
-            Oper::Exp => write!(f, "exp"),
+#[derive(Debug)]
+struct TanhOp;
+
+impl Op for TanhOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs[0].tanh()
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, out_val: f64) {
+        if !inputs.is_empty() {
+            inputs[0].aug_grad(out_grad * (1.0 - (out_val * out_val)));
         }
     }
+
+    fn symbol(&self) -> &str {
+        "tanh"
+    }
 }
 
-impl Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.0.borrow().op.is_none() {
-            write!(f, "{}", self.0.borrow().data)
-        } else {
-            write!(f, "{}", self.0.borrow().op.as_ref().unwrap())
+#[derive(Debug)]
+struct ExpOp;
+
+impl Op for ExpOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs[0].exp()
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, out_val: f64) {
+        if !inputs.is_empty() {
+            inputs[0].aug_grad(out_grad * out_val);
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        "exp"
+    }
+}
+
+#[derive(Debug)]
+struct SigmoidOp;
+
+impl Op for SigmoidOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        1.0 / (1.0 + (-inputs[0]).exp())
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, out_val: f64) {
+        if !inputs.is_empty() {
+            inputs[0].aug_grad(out_grad * out_val * (1.0 - out_val));
         }
     }
+
+    fn symbol(&self) -> &str {
+        "sigmoid"
+    }
 }
 
-impl Display for ValueData {
+#[derive(Debug)]
+struct LnOp;
+
+impl Op for LnOp {
+    fn forward(&self, inputs: &[f64]) -> f64 {
+        inputs[0].ln()
+    }
+
+    fn backward(&self, inputs: &[Value], out_grad: f64, _out_val: f64) {
+        if !inputs.is_empty() {
+            inputs[0].aug_grad(out_grad / inputs[0].val());
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        "ln"
+    }
+}
+
+impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.data)
+        if let Some(op) = &self.0.borrow().op {
+            write!(f, "{}", op.symbol())
+        } else {
+            write!(f, "{}", self.0.borrow().data)
+        }
     }
 }
 
@@ -182,12 +287,7 @@ impl Add for &Value {
     type Output = Value;
 
     fn add(self, other: Self) -> Self::Output {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data + other.0.borrow().data,
-            grad: 0.0,
-            op: Some(Oper::Sum),
-            kids: vec![self.clone_ref(), other.clone_ref()],
-        })))
+        new_node(SumOp, vec![self.clone_ref(), other.clone_ref()])
     }
 }
 
@@ -233,12 +333,7 @@ impl Sub for &Value {
     type Output = Value;
 
     fn sub(self, other: &Value) -> Self::Output {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data - other.0.borrow().data,
-            grad: 0.0,
-            op: Some(Oper::Sum),
-            kids: vec![self.clone_ref(), -&other.clone_ref()],
-        })))
+        new_node(SumOp, vec![self.clone_ref(), -&other.clone_ref()])
     }
 }
 
@@ -262,12 +357,7 @@ impl Mul for &Value {
     type Output = Value;
 
     fn mul(self, other: &Value) -> Self::Output {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data * other.0.borrow().data,
-            grad: 0.0,
-            op: Some(Oper::Mul),
-            kids: vec![self.clone_ref(), other.clone_ref()],
-        })))
+        new_node(MulOp, vec![self.clone_ref(), other.clone_ref()])
     }
 }
 
@@ -291,12 +381,7 @@ impl Div for &Value {
     type Output = Value;
 
     fn div(self, other: &Value) -> Self::Output {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data / other.0.borrow().data,
-            grad: 0.0,
-            op: Some(Oper::Mul),
-            kids: vec![self.clone_ref(), other.clone_ref().reciprocal()],
-        })))
+        new_node(MulOp, vec![self.clone_ref(), other.clone_ref().reciprocal()])
     }
 }
 
@@ -310,47 +395,43 @@ impl Neg for &Value {
 
 impl Value {
     pub fn pow(&self, exp: f64) -> Self {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data.powf(exp),
-            grad: 0.0,
-            op: Some(Oper::Pow { exp }),
-            kids: vec![self.clone_ref()],
-        })))
+        new_node(PowOp { exp }, vec![self.clone_ref()])
     }
 
     pub fn reciprocal(&self) -> Self {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: 1.0 / self.0.borrow().data,
-            grad: 0.0,
-            op: Some(Oper::Pow { exp: -1.0 }),
-            kids: vec![self.clone_ref()],
-        })))
+        new_node(PowOp { exp: -1.0 }, vec![self.clone_ref()])
     }
 
     pub fn relu(&self) -> Self {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data.max(0.0),
-            grad: 0.0,
-            op: Some(Oper::Relu),
-            kids: vec![self.clone_ref()],
-        })))
+        new_node(ReluOp, vec![self.clone_ref()])
     }
 
     pub fn tanh(&self) -> Self {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data.tanh(),
-            grad: 0.0,
-            op: Some(Oper::Tanh),
-            kids: vec![self.clone_ref()],
-        })))
+        new_node(TanhOp, vec![self.clone_ref()])
     }
 
     pub fn exp(&self) -> Self {
-        Value(Rc::new(RefCell::new(ValueData {
-            data: self.0.borrow().data.exp(),
-            grad: 0.0,
-            op: Some(Oper::Exp),
-            kids: vec![self.clone_ref()],
-        })))
+        new_node(ExpOp, vec![self.clone_ref()])
+    }
+
+    pub fn sigmoid(&self) -> Self {
+        new_node(SigmoidOp, vec![self.clone_ref()])
     }
+
+    pub fn ln(&self) -> Self {
+        new_node(LnOp, vec![self.clone_ref()])
+    }
+}
+
+pub fn softmax(logits: &[Value]) -> Vec<Value> {
+    let max_val = logits.iter().map(Value::val).fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<Value> = logits.iter().map(|l| (l - max_val).exp()).collect();
+    let sum = exps.iter().fold(Value::new(0.0), |acc, x| &acc + x);
+    let inv_sum = sum.reciprocal();
+    exps.iter().map(|e| e * &inv_sum).collect()
+}
+
+pub fn cross_entropy(logits: &[Value], target_index: usize) -> Value {
+    let probs = softmax(logits);
+    -&probs[target_index].ln()
 }